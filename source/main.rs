@@ -1,11 +1,11 @@
 use std::env::args;
 use std::fs;
 use std::io::{stdin, stdout};
+use std::process::exit;
 use std::time::Instant;
+use rsbf::vm;
 use yansi::Paint;
 
-mod vm;
-
 fn main() {
     let args: Vec<String> = args().collect();
     let stdio = stdin();
@@ -16,9 +16,17 @@ fn main() {
 
     let source_code = fs::read_to_string(&args[1]).unwrap();
     let bf_instructions = vm::lexer(&source_code);
-    let vm_instructions = vm::parse(&bf_instructions);
+    let vm_instructions = vm::parse(&bf_instructions).unwrap_or_else(|error| {
+        eprintln!("{}", Paint::red(format!("error: {error}")));
+        exit(1);
+    });
+    let ops = vm::compile(&vm_instructions);
+    let config = vm::VmConfig::default();
 
-    vm::run(&vm_instructions, &mut input, &mut output);
+    if let Err(error) = vm::run(&ops, &mut input, &mut output, &config) {
+        eprintln!("{}", Paint::red(format!("error: {error}")));
+        exit(1);
+    }
 
     println!(
         "\n  {} {} {:.3?}",
@@ -1,4 +1,80 @@
-use std::io::{BufRead, Write};
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Minimal byte-oriented reader the VM core executes against, analogous to
+/// `core_io::Read`/`std::io::Read` but local so this module has no direct
+/// dependency on `std`. Returns `Ok(None)` on EOF instead of an error.
+pub trait Read {
+    type Error;
+
+    fn read_byte(&mut self) -> Result<Option<u8>, Self::Error>;
+}
+
+/// Minimal byte-oriented writer the VM core executes against, analogous to
+/// `core_io::Write`/`std::io::Write`.
+pub trait Write {
+    type Error;
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> Read for R {
+    type Error = std::io::Error;
+
+    fn read_byte(&mut self) -> Result<Option<u8>, Self::Error> {
+        let mut buffer: [u8; 1] = [0; 1];
+
+        return match std::io::Read::read(self, &mut buffer) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(buffer[0])),
+            Err(error) => Err(error),
+        };
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Write for W {
+    type Error = std::io::Error;
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::Error> {
+        return std::io::Write::write_all(self, &[byte]);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trap {
+    /// The configured instruction budget was exhausted before the program finished.
+    BudgetExhausted,
+    /// The data pointer moved to a cell the tape cannot address.
+    PointerOutOfBounds { pointer: usize },
+}
+
+#[derive(Debug)]
+pub enum BfError<E> {
+    UnbalancedBracket { index: usize },
+    Io(E),
+    Trap(Trap),
+}
+
+impl<E: fmt::Display> fmt::Display for BfError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BfError::UnbalancedBracket { index } => {
+                write!(f, "unbalanced bracket at instruction {index}")
+            }
+            BfError::Io(error) => write!(f, "io error: {error}"),
+            BfError::Trap(Trap::BudgetExhausted) => write!(f, "instruction budget exhausted"),
+            BfError::Trap(Trap::PointerOutOfBounds { pointer }) => {
+                write!(f, "pointer out of bounds: {pointer}")
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for BfError<E> {}
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum BFInstruction {
@@ -19,9 +95,64 @@ pub enum VMInstruction {
     Print,
     Read,
     Loop(Vec<VMInstruction>),
+    SetZero,
+    MultiplyAdd { offset: isize, factor: i8 },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Op {
+    Increment(i8),
+    Move(isize),
+    Print,
+    Read,
+    SetZero,
+    MultiplyAdd { offset: isize, factor: i8 },
+    JumpIfZero(usize),
+    JumpIfNotZero(usize),
 }
 
-pub fn lexer(source_code: &String) -> Vec<BFInstruction> {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerPolicy {
+    /// Pointer movement wraps around at the tape's edges.
+    WrapPointer,
+    /// Pointer movement is clamped to the tape's edges.
+    ClampPointer,
+    /// The tape grows rightward as the pointer advances past its current end.
+    GrowTape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EofMode {
+    /// Leave the current cell as-is when the reader is exhausted.
+    Unchanged,
+    /// Set the current cell to 0 when the reader is exhausted.
+    Zero,
+    /// Set the current cell to 255 when the reader is exhausted.
+    NegativeOne,
+}
+
+#[derive(Debug, Clone)]
+pub struct VmConfig {
+    pub tape_size: usize,
+    pub pointer_policy: PointerPolicy,
+    pub eof_mode: EofMode,
+    /// Maximum number of ops `run` will execute before trapping with
+    /// `Trap::BudgetExhausted`. `None` means no limit.
+    pub instruction_budget: Option<u64>,
+}
+
+impl Default for VmConfig {
+    fn default() -> Self {
+        VmConfig {
+            tape_size: 30_000,
+            pointer_policy: PointerPolicy::WrapPointer,
+            eof_mode: EofMode::Unchanged,
+            instruction_budget: None,
+        }
+    }
+}
+
+pub fn lexer(source_code: &str) -> Vec<BFInstruction> {
     let mut bf_instructions: Vec<BFInstruction> = Vec::new();
 
     for character in source_code.chars() {
@@ -59,10 +190,12 @@ fn infer_move_direction(bf_instruction: &BFInstruction) -> isize {
     };
 }
 
-fn parse_internal(
+fn parse_internal<E>(
     bf_instructions: &[BFInstruction],
     vm_instructions: &mut Vec<VMInstruction>,
-) -> usize {
+    inside_loop: bool,
+    base_index: usize,
+) -> Result<usize, BfError<E>> {
     let mut index = 0;
 
     while index < bf_instructions.len() {
@@ -96,81 +229,312 @@ fn parse_internal(
             BFInstruction::JumpIfZero => {
                 let mut instructions_in_loop: Vec<VMInstruction> = Vec::new();
 
-                let bf_instructions_consumed =
-                    parse_internal(&bf_instructions[(index + 1)..], &mut instructions_in_loop);
+                let bf_instructions_consumed = parse_internal(
+                    &bf_instructions[(index + 1)..],
+                    &mut instructions_in_loop,
+                    true,
+                    base_index + index + 1,
+                )?;
 
                 vm_instructions.push(VMInstruction::Loop(instructions_in_loop));
 
                 index += bf_instructions_consumed;
             }
-            BFInstruction::JumpIfNotZero => return index + 1,
+            BFInstruction::JumpIfNotZero => {
+                if !inside_loop {
+                    return Err(BfError::UnbalancedBracket {
+                        index: base_index + index,
+                    });
+                }
+
+                return Ok(index + 1);
+            }
         }
 
         index += 1
     }
 
-    return index;
+    if inside_loop {
+        return Err(BfError::UnbalancedBracket {
+            index: base_index - 1,
+        });
+    }
+
+    return Ok(index);
 }
 
-pub fn parse(bf_instructions: &[BFInstruction]) -> Vec<VMInstruction> {
+pub fn parse(
+    bf_instructions: &[BFInstruction],
+) -> Result<Vec<VMInstruction>, BfError<core::convert::Infallible>> {
     let mut vm_instructions: Vec<VMInstruction> = Vec::new();
 
-    parse_internal(bf_instructions, &mut vm_instructions);
+    parse_internal(bf_instructions, &mut vm_instructions, false, 0)?;
+
+    return Ok(optimize(vm_instructions));
+}
+
+// Recognizes a balanced `[-]`/`[->++>+++<<]`-style loop body (only `Increment`
+// and `Move`, net pointer movement of 0, offset-0 net increment of -1, or of 1
+// when no other offset is touched) and returns the `MultiplyAdd`/`SetZero`
+// sequence it is equivalent to.
+fn try_optimize_loop_body(body: &[VMInstruction]) -> Option<Vec<VMInstruction>> {
+    let mut offset: isize = 0;
+    let mut deltas: BTreeMap<isize, i8> = BTreeMap::new();
 
-    return vm_instructions;
+    for vm_instruction in body {
+        match vm_instruction {
+            VMInstruction::Increment(amount) => {
+                let delta = deltas.entry(offset).or_insert(0);
+                *delta = delta.wrapping_add(*amount);
+            }
+            VMInstruction::Move(amount) => {
+                offset += amount;
+            }
+            _ => return None,
+        }
+    }
+
+    if offset != 0 {
+        return None;
+    }
+
+    match deltas.get(&0) {
+        // A `+1` loop only terminates via wraparound, running `256 - mem[ptr]`
+        // times rather than `mem[ptr]` times, so it cannot be turned into
+        // `MultiplyAdd`s against the other offsets' net increments. It only
+        // collapses to `SetZero` when there are no other offsets to multiply.
+        Some(1) if deltas.len() == 1 => {}
+        Some(-1) => {}
+        _ => return None,
+    }
+
+    let mut optimized_body = Vec::new();
+
+    for (offset, factor) in deltas {
+        if offset != 0 {
+            optimized_body.push(VMInstruction::MultiplyAdd { offset, factor });
+        }
+    }
+
+    optimized_body.push(VMInstruction::SetZero);
+
+    return Some(optimized_body);
+}
+
+fn optimize(vm_instructions: Vec<VMInstruction>) -> Vec<VMInstruction> {
+    let mut optimized_instructions = Vec::with_capacity(vm_instructions.len());
+
+    for vm_instruction in vm_instructions {
+        match vm_instruction {
+            VMInstruction::Loop(body) => {
+                let optimized_body = optimize(body);
+
+                match try_optimize_loop_body(&optimized_body) {
+                    Some(replacement) => optimized_instructions.extend(replacement),
+                    None => optimized_instructions.push(VMInstruction::Loop(optimized_body)),
+                }
+            }
+            other => optimized_instructions.push(other),
+        }
+    }
+
+    return optimized_instructions;
+}
+
+// Linearizes the (still tree-shaped, already coalesced/optimized) `VMInstruction`s
+// into flat `Op`s, replacing each `Loop` with a `JumpIfZero`/`JumpIfNotZero` pair
+// that carries the other's absolute index, so execution no longer needs to recurse.
+pub fn compile(vm_instructions: &[VMInstruction]) -> Vec<Op> {
+    let mut ops: Vec<Op> = Vec::new();
+    let mut open_brackets: Vec<usize> = Vec::new();
+
+    compile_internal(vm_instructions, &mut ops, &mut open_brackets);
+
+    return ops;
 }
 
-fn exec_vm_instructions(
+fn compile_internal(
     vm_instructions: &[VMInstruction],
-    memory: &mut [u8; 30_000],
-    data_pointer: &mut usize,
-    reader: &mut impl BufRead,
-    writer: &mut impl Write,
+    ops: &mut Vec<Op>,
+    open_brackets: &mut Vec<usize>,
 ) {
     for vm_instruction in vm_instructions {
         match vm_instruction {
-            VMInstruction::Increment(amount) => {
-                memory[*data_pointer] = memory[*data_pointer].wrapping_add_signed(*amount);
+            VMInstruction::Increment(amount) => ops.push(Op::Increment(*amount)),
+            VMInstruction::Move(amount) => ops.push(Op::Move(*amount)),
+            VMInstruction::Print => ops.push(Op::Print),
+            VMInstruction::Read => ops.push(Op::Read),
+            VMInstruction::SetZero => ops.push(Op::SetZero),
+            VMInstruction::MultiplyAdd { offset, factor } => ops.push(Op::MultiplyAdd {
+                offset: *offset,
+                factor: *factor,
+            }),
+            VMInstruction::Loop(body) => {
+                open_brackets.push(ops.len());
+                ops.push(Op::JumpIfZero(0));
+
+                compile_internal(body, ops, open_brackets);
+
+                let jump_if_zero_index = open_brackets.pop().expect("unbalanced loop");
+                let jump_if_not_zero_index = ops.len();
+
+                ops.push(Op::JumpIfNotZero(jump_if_zero_index));
+                ops[jump_if_zero_index] = Op::JumpIfZero(jump_if_not_zero_index);
             }
-            VMInstruction::Move(amount) => {
-                *data_pointer = data_pointer.wrapping_add_signed(*amount);
+        }
+    }
+}
+
+fn cell<E>(memory: &[u8], pointer: usize) -> Result<u8, BfError<E>> {
+    return memory
+        .get(pointer)
+        .copied()
+        .ok_or(BfError::Trap(Trap::PointerOutOfBounds { pointer }));
+}
+
+fn cell_mut<E>(memory: &mut [u8], pointer: usize) -> Result<&mut u8, BfError<E>> {
+    return memory
+        .get_mut(pointer)
+        .ok_or(BfError::Trap(Trap::PointerOutOfBounds { pointer }));
+}
+
+// Resolves `current + amount` against the tape according to the configured
+// `PointerPolicy`, growing `memory` rightward when that policy is `GrowTape`.
+fn resolve_pointer<E>(
+    current: usize,
+    amount: isize,
+    memory: &mut Vec<u8>,
+    config: &VmConfig,
+) -> Result<usize, BfError<E>> {
+    let next = current as isize + amount;
+
+    return match config.pointer_policy {
+        PointerPolicy::WrapPointer => {
+            let tape_size = config.tape_size as isize;
+            let wrapped = next.rem_euclid(tape_size);
+
+            Ok(wrapped as usize)
+        }
+        PointerPolicy::ClampPointer => {
+            Ok(next.clamp(0, config.tape_size as isize - 1) as usize)
+        }
+        PointerPolicy::GrowTape => {
+            if next < 0 {
+                return Err(BfError::Trap(Trap::PointerOutOfBounds { pointer: current }));
+            }
+
+            let next = next as usize;
+
+            if next >= memory.len() {
+                memory.resize(next + 1, 0);
+            }
+
+            Ok(next)
+        }
+    };
+}
+
+fn exec_ops<R, W>(
+    ops: &[Op],
+    memory: &mut Vec<u8>,
+    data_pointer: &mut usize,
+    reader: &mut R,
+    writer: &mut W,
+    config: &VmConfig,
+    executed: &mut u64,
+) -> Result<(), BfError<R::Error>>
+where
+    R: Read,
+    W: Write<Error = R::Error>,
+{
+    let mut program_counter = 0;
+
+    while program_counter < ops.len() {
+        if let Some(budget) = config.instruction_budget {
+            if *executed >= budget {
+                return Err(BfError::Trap(Trap::BudgetExhausted));
             }
-            VMInstruction::Print => {
-                write!(writer, "{}", memory[*data_pointer] as char).expect("cannot write");
+        }
+
+        match &ops[program_counter] {
+            Op::Increment(amount) => {
+                let current = cell_mut(memory, *data_pointer)?;
+                *current = current.wrapping_add_signed(*amount);
             }
-            VMInstruction::Read => {
-                let mut input: [u8; 1] = [0; 1];
+            Op::Move(amount) => {
+                *data_pointer = resolve_pointer(*data_pointer, *amount, memory, config)?;
+            }
+            Op::Print => {
+                let byte = cell(memory, *data_pointer)?;
 
-                reader.read_exact(&mut input).expect("cannot read");
+                writer.write_byte(byte).map_err(BfError::Io)?;
+            }
+            Op::Read => match reader.read_byte().map_err(BfError::Io)? {
+                Some(byte) => *cell_mut(memory, *data_pointer)? = byte,
+                None => {
+                    let current = cell_mut(memory, *data_pointer)?;
+
+                    match config.eof_mode {
+                        EofMode::Unchanged => {}
+                        EofMode::Zero => *current = 0,
+                        EofMode::NegativeOne => *current = 255,
+                    }
+                }
+            },
+            Op::SetZero => {
+                *cell_mut(memory, *data_pointer)? = 0;
+            }
+            Op::MultiplyAdd { offset, factor } => {
+                let target = resolve_pointer(*data_pointer, *offset, memory, config)?;
+                let value = cell(memory, *data_pointer)?.wrapping_mul(*factor as u8);
 
-                memory[*data_pointer] = input[0];
+                let target_cell = cell_mut(memory, target)?;
+                *target_cell = target_cell.wrapping_add(value);
+            }
+            Op::JumpIfZero(target) => {
+                if cell(memory, *data_pointer)? == 0 {
+                    program_counter = *target;
+                }
             }
-            VMInstruction::Loop(vm_instructions) => {
-                while memory[*data_pointer] != 0 {
-                    exec_vm_instructions(vm_instructions, memory, data_pointer, reader, writer);
+            Op::JumpIfNotZero(target) => {
+                if cell(memory, *data_pointer)? != 0 {
+                    program_counter = *target;
                 }
             }
         }
+
+        *executed += 1;
+        program_counter += 1;
     }
+
+    return Ok(());
 }
 
-pub fn run(
-    vm_instructions: &[VMInstruction],
-    reader: &mut impl BufRead,
-    writer: &mut impl Write,
-) -> [u8; 30_000] {
-    let mut memory: [u8; 30_000] = [0; 30_000];
+pub fn run<R, W>(
+    ops: &[Op],
+    reader: &mut R,
+    writer: &mut W,
+    config: &VmConfig,
+) -> Result<Vec<u8>, BfError<R::Error>>
+where
+    R: Read,
+    W: Write<Error = R::Error>,
+{
+    let mut memory: Vec<u8> = vec![0; config.tape_size];
     let mut data_pointer: usize = 0;
+    let mut executed: u64 = 0;
 
-    exec_vm_instructions(
-        vm_instructions,
+    exec_ops(
+        ops,
         &mut memory,
         &mut data_pointer,
         reader,
         writer,
-    );
+        config,
+        &mut executed,
+    )?;
 
-    return memory;
+    return Ok(memory);
 }
 
 #[cfg(test)]
@@ -180,7 +544,7 @@ mod tests {
 
     use crate::vm::{lexer, BFInstruction, VMInstruction};
 
-    use super::{parse, run};
+    use super::{compile, parse, run, BfError, EofMode, PointerPolicy, Trap, VmConfig};
 
     #[test]
     fn lexer_converts_source_to_bf_instructions() {
@@ -309,23 +673,28 @@ mod tests {
     fn parse_converts_bf_instructions_to_vm_instructions() {
         let source_code = fs::read_to_string("programs/hello-world.bf").unwrap();
         let bf_instructions = lexer(&source_code);
-        let vm_instructions = parse(&bf_instructions);
+        let vm_instructions = parse(&bf_instructions).expect("cannot parse");
 
         assert_eq!(
             vec![
                 VMInstruction::Increment(10),
-                VMInstruction::Loop(vec![
-                    VMInstruction::Move(1),
-                    VMInstruction::Increment(7),
-                    VMInstruction::Move(1),
-                    VMInstruction::Increment(10),
-                    VMInstruction::Move(1),
-                    VMInstruction::Increment(3),
-                    VMInstruction::Move(1),
-                    VMInstruction::Increment(1),
-                    VMInstruction::Move(-4),
-                    VMInstruction::Increment(-1),
-                ]),
+                VMInstruction::MultiplyAdd {
+                    offset: 1,
+                    factor: 7,
+                },
+                VMInstruction::MultiplyAdd {
+                    offset: 2,
+                    factor: 10,
+                },
+                VMInstruction::MultiplyAdd {
+                    offset: 3,
+                    factor: 3,
+                },
+                VMInstruction::MultiplyAdd {
+                    offset: 4,
+                    factor: 1,
+                },
+                VMInstruction::SetZero,
                 VMInstruction::Move(1),
                 VMInstruction::Increment(2),
                 VMInstruction::Print,
@@ -368,13 +737,226 @@ mod tests {
 
         let source_code = fs::read_to_string("programs/hello-world.bf").unwrap();
         let bf_instructions = lexer(&source_code);
-        let vm_instructions = parse(&bf_instructions);
+        let vm_instructions = parse(&bf_instructions).expect("cannot parse");
+        let ops = compile(&vm_instructions);
 
-        run(&vm_instructions, &mut input, &mut output);
+        run(&ops, &mut input, &mut output, &VmConfig::default()).expect("cannot run");
 
         assert_eq!(
             "Hello World!\n",
             String::from_utf8(output).expect("cannot convert output")
         )
     }
+
+    #[test]
+    fn parse_reports_unbalanced_bracket_for_stray_close() {
+        let bf_instructions = lexer("]");
+
+        assert!(matches!(
+            parse(&bf_instructions),
+            Err(BfError::UnbalancedBracket { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn parse_reports_unbalanced_bracket_for_unclosed_open() {
+        let bf_instructions = lexer("[");
+
+        assert!(matches!(
+            parse(&bf_instructions),
+            Err(BfError::UnbalancedBracket { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn parse_reports_unbalanced_bracket_for_extra_close_after_balanced_loop() {
+        let bf_instructions = lexer("[]]");
+
+        assert!(matches!(
+            parse(&bf_instructions),
+            Err(BfError::UnbalancedBracket { index: 2 })
+        ));
+    }
+
+    struct FailingReader;
+
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, _buffer: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("read failed"))
+        }
+    }
+
+    #[test]
+    fn run_propagates_io_errors_instead_of_panicking() {
+        let mut input = FailingReader;
+        let mut output = Vec::new();
+
+        let bf_instructions = lexer(",");
+        let vm_instructions = parse(&bf_instructions).expect("cannot parse");
+        let ops = compile(&vm_instructions);
+
+        assert!(matches!(
+            run(&ops, &mut input, &mut output, &VmConfig::default()),
+            Err(BfError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn run_leaves_cell_unchanged_on_eof_with_unchanged_mode() {
+        let mut input: &[u8] = &[];
+        let mut output = Vec::new();
+
+        let bf_instructions = lexer("+++,");
+        let vm_instructions = parse(&bf_instructions).expect("cannot parse");
+        let ops = compile(&vm_instructions);
+
+        let config = VmConfig {
+            eof_mode: EofMode::Unchanged,
+            ..VmConfig::default()
+        };
+
+        let memory = run(&ops, &mut input, &mut output, &config).expect("cannot run");
+
+        assert_eq!(3, memory[0]);
+    }
+
+    #[test]
+    fn run_zeroes_cell_on_eof_with_zero_mode() {
+        let mut input: &[u8] = &[];
+        let mut output = Vec::new();
+
+        let bf_instructions = lexer("+++,");
+        let vm_instructions = parse(&bf_instructions).expect("cannot parse");
+        let ops = compile(&vm_instructions);
+
+        let config = VmConfig {
+            eof_mode: EofMode::Zero,
+            ..VmConfig::default()
+        };
+
+        let memory = run(&ops, &mut input, &mut output, &config).expect("cannot run");
+
+        assert_eq!(0, memory[0]);
+    }
+
+    #[test]
+    fn run_sets_cell_to_255_on_eof_with_negative_one_mode() {
+        let mut input: &[u8] = &[];
+        let mut output = Vec::new();
+
+        let bf_instructions = lexer("+++,");
+        let vm_instructions = parse(&bf_instructions).expect("cannot parse");
+        let ops = compile(&vm_instructions);
+
+        let config = VmConfig {
+            eof_mode: EofMode::NegativeOne,
+            ..VmConfig::default()
+        };
+
+        let memory = run(&ops, &mut input, &mut output, &config).expect("cannot run");
+
+        assert_eq!(255, memory[0]);
+    }
+
+    #[test]
+    fn run_stops_with_budget_exhausted_trap_instead_of_hanging() {
+        let mut input = "".as_bytes();
+        let mut output = Vec::new();
+
+        let bf_instructions = lexer("+[]");
+        let vm_instructions = parse(&bf_instructions).expect("cannot parse");
+        let ops = compile(&vm_instructions);
+
+        let config = VmConfig {
+            instruction_budget: Some(100),
+            ..VmConfig::default()
+        };
+
+        assert!(matches!(
+            run(&ops, &mut input, &mut output, &config),
+            Err(BfError::Trap(Trap::BudgetExhausted))
+        ));
+    }
+
+    #[test]
+    fn run_wraps_a_left_move_from_cell_zero_with_wrap_pointer() {
+        let mut input = "".as_bytes();
+        let mut output = Vec::new();
+
+        let bf_instructions = lexer("<+");
+        let vm_instructions = parse(&bf_instructions).expect("cannot parse");
+        let ops = compile(&vm_instructions);
+
+        let config = VmConfig {
+            tape_size: 4,
+            pointer_policy: PointerPolicy::WrapPointer,
+            ..VmConfig::default()
+        };
+
+        let memory = run(&ops, &mut input, &mut output, &config).expect("cannot run");
+
+        assert_eq!(1, memory[3]);
+    }
+
+    #[test]
+    fn run_clamps_a_left_move_from_cell_zero_with_clamp_pointer() {
+        let mut input = "".as_bytes();
+        let mut output = Vec::new();
+
+        let bf_instructions = lexer("<+");
+        let vm_instructions = parse(&bf_instructions).expect("cannot parse");
+        let ops = compile(&vm_instructions);
+
+        let config = VmConfig {
+            tape_size: 4,
+            pointer_policy: PointerPolicy::ClampPointer,
+            ..VmConfig::default()
+        };
+
+        let memory = run(&ops, &mut input, &mut output, &config).expect("cannot run");
+
+        assert_eq!(1, memory[0]);
+    }
+
+    #[test]
+    fn run_traps_instead_of_panicking_on_a_left_move_from_cell_zero_with_grow_tape() {
+        let mut input = "".as_bytes();
+        let mut output = Vec::new();
+
+        let bf_instructions = lexer("<");
+        let vm_instructions = parse(&bf_instructions).expect("cannot parse");
+        let ops = compile(&vm_instructions);
+
+        let config = VmConfig {
+            tape_size: 4,
+            pointer_policy: PointerPolicy::GrowTape,
+            ..VmConfig::default()
+        };
+
+        assert!(matches!(
+            run(&ops, &mut input, &mut output, &config),
+            Err(BfError::Trap(Trap::PointerOutOfBounds { pointer: 0 }))
+        ));
+    }
+
+    #[test]
+    fn run_grows_the_tape_rightward_past_its_configured_size() {
+        let mut input = "".as_bytes();
+        let mut output = Vec::new();
+
+        let bf_instructions = lexer(">>>>>+");
+        let vm_instructions = parse(&bf_instructions).expect("cannot parse");
+        let ops = compile(&vm_instructions);
+
+        let config = VmConfig {
+            tape_size: 4,
+            pointer_policy: PointerPolicy::GrowTape,
+            ..VmConfig::default()
+        };
+
+        let memory = run(&ops, &mut input, &mut output, &config).expect("cannot run");
+
+        assert_eq!(6, memory.len());
+        assert_eq!(1, memory[5]);
+    }
 }